@@ -1,17 +1,16 @@
 use base62;
 use base64;
 use digest::{Digest, Mac};
+use ed25519_dalek;
 use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
 use hmac::Hmac;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use sha2::Sha256;
+use sha2::{Sha256, Sha384, Sha512};
 use std::io::{Read, Write};
 pub use time::Duration; // re-export
 use time::OffsetDateTime;
 
-type HmacSha256 = Hmac<Sha256>;
-
 #[cfg(feature = "python")]
 mod python;
 
@@ -26,6 +25,172 @@ pub enum SignatureError {
     ObjectFormatError,
 }
 
+/// How the signer derives its inner HMAC key from `key` and `salt`.
+///
+/// Django and itsdangerous each pick their own scheme, so interop with a
+/// token minted by another ecosystem requires matching whichever mode it
+/// used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDerivation {
+    /// `digest(salt || key)`, using whichever [`DigestAlgorithm`] the signer
+    /// is configured with (SHA-256 by default).
+    Concat,
+    /// `digest(salt || "signer" || key)`, Django's `TimestampSigner.salt_key`,
+    /// using whichever [`DigestAlgorithm`] the signer is configured with
+    /// (SHA-256 by default).
+    DjangoConcat,
+    /// `HMAC-digest(key=key, msg=salt)`, using whichever [`DigestAlgorithm`]
+    /// the signer is configured with (SHA-256 by default).
+    Hmac,
+    /// Use `key` verbatim, without deriving anything.
+    None,
+}
+
+impl Default for KeyDerivation {
+    fn default() -> Self {
+        KeyDerivation::DjangoConcat
+    }
+}
+
+/// The hash used both to derive the inner key and as the outer HMAC, per
+/// the HS256/HS384/HS512 family other token ecosystems expose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Default for DigestAlgorithm {
+    fn default() -> Self {
+        DigestAlgorithm::Sha256
+    }
+}
+
+fn hash_digest<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = D::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().to_vec()
+}
+
+fn hmac_digest<D>(key: &[u8], value: &[u8]) -> Vec<u8>
+where
+    Hmac<D>: Mac,
+{
+    let mut mac = Hmac::<D>::new_from_slice(key).unwrap();
+    mac.update(value);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hmac_verify<D>(key: &[u8], value: &[u8], sig: &[u8]) -> bool
+where
+    Hmac<D>: Mac,
+{
+    match Hmac::<D>::new_from_slice(key) {
+        Ok(mut mac) => {
+            mac.update(value);
+            mac.verify_slice(sig).is_ok()
+        }
+        Err(_) => false,
+    }
+}
+
+fn derive_key(
+    key: &[u8],
+    salt: &[u8],
+    key_derivation: KeyDerivation,
+    digest_algorithm: DigestAlgorithm,
+) -> Vec<u8> {
+    match key_derivation {
+        KeyDerivation::Concat => match digest_algorithm {
+            DigestAlgorithm::Sha256 => hash_digest::<Sha256>(&[salt, key]),
+            DigestAlgorithm::Sha384 => hash_digest::<Sha384>(&[salt, key]),
+            DigestAlgorithm::Sha512 => hash_digest::<Sha512>(&[salt, key]),
+        },
+        KeyDerivation::DjangoConcat => {
+            // https://github.com/django/django/blob/ca04659b4b3f042c1bc7e557c25ed91e3c56c745/django/core/signing.py#L160
+            let mut new_salt = Vec::with_capacity(salt.len() + 6);
+            new_salt.extend_from_slice(salt);
+            new_salt.extend(b"signer");
+
+            match digest_algorithm {
+                DigestAlgorithm::Sha256 => hash_digest::<Sha256>(&[&new_salt, key]),
+                DigestAlgorithm::Sha384 => hash_digest::<Sha384>(&[&new_salt, key]),
+                DigestAlgorithm::Sha512 => hash_digest::<Sha512>(&[&new_salt, key]),
+            }
+        }
+        KeyDerivation::Hmac => match digest_algorithm {
+            DigestAlgorithm::Sha256 => hmac_digest::<Sha256>(key, salt),
+            DigestAlgorithm::Sha384 => hmac_digest::<Sha384>(key, salt),
+            DigestAlgorithm::Sha512 => hmac_digest::<Sha512>(key, salt),
+        },
+        KeyDerivation::None => key.to_vec(),
+    }
+}
+
+/// How a signed object's payload is turned into bytes before compression
+/// and signing, and back again afterwards.
+///
+/// `encode_object`/`decode_object` always normalize through
+/// [`serde_json::Value`] first, so any `Serialize`/`DeserializeOwned` type
+/// works with whichever wire format the serializer implements underneath.
+pub trait PayloadSerializer {
+    fn serialize(&self, value: &serde_json::Value) -> Vec<u8>;
+    fn deserialize(&self, bytes: &[u8]) -> Result<serde_json::Value, SignatureError>;
+}
+
+/// The original, default wire format: plain `serde_json`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSerializer;
+
+impl PayloadSerializer for JsonSerializer {
+    fn serialize(&self, value: &serde_json::Value) -> Vec<u8> {
+        serde_json::to_vec(value).unwrap()
+    }
+    fn deserialize(&self, bytes: &[u8]) -> Result<serde_json::Value, SignatureError> {
+        serde_json::from_slice(bytes).map_err(|_| SignatureError::ObjectFormatError)
+    }
+}
+
+/// Canonical JSON: object keys sorted lexicographically with compact
+/// separators, so the same logical payload produces identical bytes
+/// regardless of which implementation serialized it — the same
+/// stringify-then-hash discipline SSB feed messages rely on for signature
+/// stability across implementations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanonicalJsonSerializer;
+
+impl CanonicalJsonSerializer {
+    fn canonicalize(value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Array(values) => {
+                serde_json::Value::Array(values.iter().map(Self::canonicalize).collect())
+            }
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                let mut canonical = serde_json::Map::new();
+                for (key, value) in entries {
+                    canonical.insert(key.clone(), Self::canonicalize(value));
+                }
+                serde_json::Value::Object(canonical)
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+impl PayloadSerializer for CanonicalJsonSerializer {
+    fn serialize(&self, value: &serde_json::Value) -> Vec<u8> {
+        serde_json::to_vec(&Self::canonicalize(value)).unwrap()
+    }
+    fn deserialize(&self, bytes: &[u8]) -> Result<serde_json::Value, SignatureError> {
+        serde_json::from_slice(bytes).map_err(|_| SignatureError::ObjectFormatError)
+    }
+}
+
 pub trait Signer {
     fn sign(&self, value: String) -> String;
 
@@ -57,76 +222,153 @@ pub trait TimedSigner: Signer {
 }
 
 pub struct BaseSigner {
-    key: Vec<u8>,
+    // `keys[0]` is the current key used for signing; the rest are fallback
+    // keys tried in order when verifying, so a rotated secret doesn't
+    // invalidate outstanding tokens.
+    keys: Vec<Vec<u8>>,
+    serializer: Box<dyn PayloadSerializer>,
+    digest_algorithm: DigestAlgorithm,
 }
 
 impl BaseSigner {
-    pub fn new(key: &[u8], salt: &[u8]) -> Self {
-        // https://github.com/django/django/blob/ca04659b4b3f042c1bc7e557c25ed91e3c56c745/django/core/signing.py#L160
-        let mut new_salt = Vec::with_capacity(salt.len() + 6);
-        new_salt.extend_from_slice(salt);
-        new_salt.extend(b"signer");
-
-        let mut inner_hasher = Sha256::new();
-        inner_hasher.update(&new_salt[..]);
-        inner_hasher.update(key);
-
+    pub fn new(
+        key: &[u8],
+        fallback_keys: &[&[u8]],
+        salt: &[u8],
+        key_derivation: KeyDerivation,
+        serializer: Box<dyn PayloadSerializer>,
+        digest_algorithm: DigestAlgorithm,
+    ) -> Self {
+        let keys = std::iter::once(key)
+            .chain(fallback_keys.iter().copied())
+            .map(|key| derive_key(key, salt, key_derivation, digest_algorithm))
+            .collect();
         Self {
-            key: inner_hasher.finalize().to_vec(),
+            keys,
+            serializer,
+            digest_algorithm,
         }
     }
-    fn get_mac_with_value(&self, value: &[u8]) -> HmacSha256 {
-        let mut mac = HmacSha256::new_from_slice(&self.key[..]).unwrap();
-        mac.update(value);
-        mac
+    fn mac_bytes(&self, key: &[u8], value: &[u8]) -> Vec<u8> {
+        match self.digest_algorithm {
+            DigestAlgorithm::Sha256 => hmac_digest::<Sha256>(key, value),
+            DigestAlgorithm::Sha384 => hmac_digest::<Sha384>(key, value),
+            DigestAlgorithm::Sha512 => hmac_digest::<Sha512>(key, value),
+        }
+    }
+    fn mac_verify(&self, key: &[u8], value: &[u8], sig: &[u8]) -> bool {
+        match self.digest_algorithm {
+            DigestAlgorithm::Sha256 => hmac_verify::<Sha256>(key, value, sig),
+            DigestAlgorithm::Sha384 => hmac_verify::<Sha384>(key, value, sig),
+            DigestAlgorithm::Sha512 => hmac_verify::<Sha512>(key, value, sig),
+        }
     }
     fn encoded_signature(&self, value: &[u8]) -> String {
-        let mac = self.get_mac_with_value(value);
-        base64::encode_config(mac.finalize().into_bytes(), base64::URL_SAFE_NO_PAD)
+        let mac = self.mac_bytes(&self.keys[0], value);
+        base64::encode_config(mac, base64::URL_SAFE_NO_PAD)
     }
 
     pub fn decode_object<T>(&self, value: String) -> Result<T, SignatureError>
     where
         T: DeserializeOwned,
     {
-        let (decompress, encoded_value) = match value.strip_prefix(".") {
-            Some(remainder) => (true, remainder.as_bytes()),
-            None => (false, value.as_bytes()),
-        };
-        let mut decoded_value =
-            base64::decode_config(encoded_value, base64::URL_SAFE_NO_PAD).unwrap();
-        if decompress {
-            let mut decoder = ZlibDecoder::new(&decoded_value[..]);
-            let mut unpacked = String::new();
-            decoder.read_to_string(&mut unpacked).unwrap();
-            decoded_value = unpacked.into();
-        }
-        match serde_json::from_slice(&decoded_value[..]) {
-            Ok(obj) => Ok(obj),
-            Err(_) => Err(SignatureError::ObjectFormatError),
-        }
+        decode_object(value, self.serializer.as_ref())
     }
 
     pub fn encode_object<T>(&self, obj: T, compress: bool) -> String
     where
         T: Serialize,
     {
-        let mut value = serde_json::to_vec(&obj).unwrap();
-        let mut is_compressed = false;
-        if compress {
-            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-            encoder.write(&value[..]).unwrap();
-            let compressed = encoder.finish().unwrap();
-            if compressed.len() < value.len() - 1 {
-                value = compressed;
-                is_compressed = true;
-            }
+        encode_object(obj, compress, self.serializer.as_ref())
+    }
+}
+
+// Object encoding/compression is independent of the authenticator (HMAC,
+// Ed25519, ...), so it lives as free functions that every signer can share.
+fn decode_object<T>(value: String, serializer: &dyn PayloadSerializer) -> Result<T, SignatureError>
+where
+    T: DeserializeOwned,
+{
+    let (decompress, encoded_value) = match value.strip_prefix(".") {
+        Some(remainder) => (true, remainder.as_bytes()),
+        None => (false, value.as_bytes()),
+    };
+    let mut decoded_value = match base64::decode_config(encoded_value, base64::URL_SAFE_NO_PAD) {
+        Ok(decoded_value) => decoded_value,
+        Err(_) => return Err(SignatureError::FormatError),
+    };
+    if decompress {
+        let mut decoder = ZlibDecoder::new(&decoded_value[..]);
+        let mut unpacked = Vec::new();
+        if decoder.read_to_end(&mut unpacked).is_err() {
+            return Err(SignatureError::ObjectFormatError);
         }
-        let mut value = base64::encode_config(value, base64::URL_SAFE_NO_PAD);
-        if is_compressed {
-            value.insert(0, '.');
+        decoded_value = unpacked;
+    }
+    let value = serializer.deserialize(&decoded_value[..])?;
+    serde_json::from_value(value).map_err(|_| SignatureError::ObjectFormatError)
+}
+
+fn encode_object<T>(obj: T, compress: bool, serializer: &dyn PayloadSerializer) -> String
+where
+    T: Serialize,
+{
+    let value = serde_json::to_value(&obj).unwrap();
+    let mut value = serializer.serialize(&value);
+    let mut is_compressed = false;
+    if compress {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write(&value[..]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        if compressed.len() < value.len() - 1 {
+            value = compressed;
+            is_compressed = true;
+        }
+    }
+    let mut value = base64::encode_config(value, base64::URL_SAFE_NO_PAD);
+    if is_compressed {
+        value.insert(0, '.');
+    }
+    value
+}
+
+// The `value:timestamp` framing `TimestampSigner` and `Ed25519TimestampSigner`
+// add on top of their inner signer is independent of the authenticator as
+// well, so it lives here rather than in each signer's `sign`/`unsign`.
+fn wrap_with_timestamp(value: String) -> String {
+    let timestamp = OffsetDateTime::now_utc().unix_timestamp() as u64;
+    format!("{}:{}", value, base62::encode(timestamp))
+}
+
+fn strip_timestamp(timestamped_value: String) -> Result<String, SignatureError> {
+    if let Some((value, _)) = timestamped_value.rsplit_once(":") {
+        Ok(value.to_string())
+    } else {
+        Err(SignatureError::MissingTimestamp)
+    }
+}
+
+fn check_timestamp(
+    timestamped_value: String,
+    max_age: Duration,
+) -> Result<String, SignatureError> {
+    if let Some((value, timestamp)) = timestamped_value.rsplit_once(":") {
+        if let Ok(timestamp) = base62::decode(timestamp) {
+            if let Ok(timestamp) = OffsetDateTime::from_unix_timestamp(timestamp as i64) {
+                let distance = OffsetDateTime::now_utc() - timestamp;
+                if distance <= max_age {
+                    Ok(value.to_string())
+                } else {
+                    Err(SignatureError::SignatureExpired)
+                }
+            } else {
+                Err(SignatureError::TimestampFormatError)
+            }
+        } else {
+            Err(SignatureError::TimestampFormatError)
         }
-        value
+    } else {
+        Err(SignatureError::MissingTimestamp)
     }
 }
 
@@ -137,8 +379,11 @@ impl Signer for BaseSigner {
     fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
         if let Some((value, sig)) = signed_value.rsplit_once(":") {
             if let Ok(decoded_sig) = base64::decode_config(sig, base64::URL_SAFE_NO_PAD) {
-                let mac = self.get_mac_with_value(value.as_bytes());
-                if let Ok(_) = mac.verify_slice(&decoded_sig[..]) {
+                let verified = self
+                    .keys
+                    .iter()
+                    .any(|key| self.mac_verify(key, value.as_bytes(), &decoded_sig[..]));
+                if verified {
                     Ok(value.to_string())
                 } else {
                     Err(SignatureError::InvalidSignature)
@@ -175,31 +420,33 @@ pub struct TimestampSigner {
 }
 
 impl TimestampSigner {
-    pub fn new(key: &[u8], salt: &[u8]) -> Self {
+    pub fn new(
+        key: &[u8],
+        fallback_keys: &[&[u8]],
+        salt: &[u8],
+        key_derivation: KeyDerivation,
+        serializer: Box<dyn PayloadSerializer>,
+        digest_algorithm: DigestAlgorithm,
+    ) -> Self {
         Self {
-            inner: BaseSigner::new(key, salt),
+            inner: BaseSigner::new(
+                key,
+                fallback_keys,
+                salt,
+                key_derivation,
+                serializer,
+                digest_algorithm,
+            ),
         }
     }
 }
 
 impl Signer for TimestampSigner {
     fn sign(&self, value: String) -> String {
-        let timestamp = OffsetDateTime::now_utc().unix_timestamp() as u64;
-        let value = format!("{}:{}", value, base62::encode(timestamp));
-        self.inner.sign(value)
+        self.inner.sign(wrap_with_timestamp(value))
     }
     fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
-        let unsigned = self.inner.unsign(signed_value);
-        match unsigned {
-            Err(e) => Err(e),
-            Ok(timestamped_value) => {
-                if let Some((value, _)) = timestamped_value.rsplit_once(":") {
-                    Ok(value.to_string())
-                } else {
-                    Err(SignatureError::MissingTimestamp)
-                }
-            }
-        }
+        self.inner.unsign(signed_value).and_then(strip_timestamp)
     }
 
     fn sign_object<T>(&self, obj: T, compress: bool) -> String
@@ -227,33 +474,285 @@ impl TimedSigner for TimestampSigner {
         signed_value: String,
         max_age: Duration,
     ) -> Result<String, SignatureError> {
-        let unsigned = self.inner.unsign(signed_value);
-        match unsigned {
+        self.inner
+            .unsign(signed_value)
+            .and_then(|timestamped_value| check_timestamp(timestamped_value, max_age))
+    }
+
+    fn unsign_object_with_age<T>(
+        &self,
+        signed_value: String,
+        max_age: Duration,
+    ) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign_with_age(signed_value, max_age) {
+            Ok(value) => self.inner.decode_object(value),
             Err(e) => Err(e),
-            Ok(timestamped_value) => {
-                if let Some((value, timestamp)) = timestamped_value.rsplit_once(":") {
-                    if let Ok(timestamp) = base62::decode(timestamp) {
-                        if let Ok(timestamp) = OffsetDateTime::from_unix_timestamp(timestamp as i64)
-                        {
-                            let distance = OffsetDateTime::now_utc() - timestamp;
-                            if distance <= max_age {
-                                Ok(value.to_string())
-                            } else {
-                                Err(SignatureError::SignatureExpired)
-                            }
-                        } else {
-                            Err(SignatureError::TimestampFormatError)
-                        }
-                    } else {
-                        Err(SignatureError::TimestampFormatError)
-                    }
-                } else {
-                    Err(SignatureError::MissingTimestamp)
+        }
+    }
+}
+
+/// An asymmetric counterpart to [`BaseSigner`] that can both mint and check
+/// detached Ed25519 signatures over the value, rather than an HMAC.
+///
+/// For a verify-only instance that can't mint tokens — e.g. one handed out
+/// to clients that should never be able to forge one — use
+/// [`Ed25519Verifier`] instead, which has no signing key to misuse.
+pub struct Ed25519Signer {
+    signing_key: ed25519_dalek::SigningKey,
+    serializer: Box<dyn PayloadSerializer>,
+}
+
+impl Ed25519Signer {
+    pub fn new(
+        signing_key: ed25519_dalek::SigningKey,
+        serializer: Box<dyn PayloadSerializer>,
+    ) -> Self {
+        Self {
+            signing_key,
+            serializer,
+        }
+    }
+
+    /// The public key tokens from this signer can be checked with, e.g. to
+    /// construct a matching [`Ed25519Verifier`].
+    pub fn verifying_key(&self) -> ed25519_dalek::VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    fn encoded_signature(&self, value: &[u8]) -> String {
+        use ed25519_dalek::Signer as _;
+
+        let signature = self.signing_key.sign(value);
+        base64::encode_config(signature.to_bytes(), base64::URL_SAFE_NO_PAD)
+    }
+
+    pub fn decode_object<T>(&self, value: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        decode_object(value, self.serializer.as_ref())
+    }
+
+    pub fn encode_object<T>(&self, obj: T, compress: bool) -> String
+    where
+        T: Serialize,
+    {
+        encode_object(obj, compress, self.serializer.as_ref())
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn sign(&self, value: String) -> String {
+        format!("{}:{}", value, self.encoded_signature(value.as_bytes()))
+    }
+    fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
+        ed25519_unsign(&self.verifying_key(), signed_value)
+    }
+
+    fn sign_object<T>(&self, obj: T, compress: bool) -> String
+    where
+        T: Serialize,
+    {
+        let value = self.encode_object(obj, compress);
+        self.sign(value)
+    }
+    fn unsign_object<T>(&self, signed_object: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign(signed_object) {
+            Ok(value) => self.decode_object(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Shared between `Ed25519Signer` (whose verifying key is derived from its
+// signing key) and `Ed25519Verifier` (which only ever holds the verifying
+// key), so the two don't each carry their own copy of signature checking.
+fn ed25519_unsign(
+    verifying_key: &ed25519_dalek::VerifyingKey,
+    signed_value: String,
+) -> Result<String, SignatureError> {
+    use ed25519_dalek::Verifier as _;
+
+    if let Some((value, sig)) = signed_value.rsplit_once(":") {
+        if let Ok(decoded_sig) = base64::decode_config(sig, base64::URL_SAFE_NO_PAD) {
+            let verified = match <[u8; 64]>::try_from(&decoded_sig[..]) {
+                Ok(sig_bytes) => {
+                    let signature = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+                    verifying_key.verify(value.as_bytes(), &signature).is_ok()
                 }
+                Err(_) => false,
+            };
+            if verified {
+                Ok(value.to_string())
+            } else {
+                Err(SignatureError::InvalidSignature)
             }
+        } else {
+            Err(SignatureError::FormatError)
+        }
+    } else {
+        Err(SignatureError::MissingSeparator)
+    }
+}
+
+/// A verify-only counterpart to [`Ed25519Signer`]: holds just the public
+/// key, so it can check tokens it could never have minted — e.g. an
+/// instance handed out to clients that should never be able to forge one.
+pub struct Ed25519Verifier {
+    verifying_key: ed25519_dalek::VerifyingKey,
+    serializer: Box<dyn PayloadSerializer>,
+}
+
+impl Ed25519Verifier {
+    pub fn new(
+        verifying_key: ed25519_dalek::VerifyingKey,
+        serializer: Box<dyn PayloadSerializer>,
+    ) -> Self {
+        Self {
+            verifying_key,
+            serializer,
         }
     }
 
+    pub fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
+        ed25519_unsign(&self.verifying_key, signed_value)
+    }
+
+    pub fn decode_object<T>(&self, value: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        decode_object(value, self.serializer.as_ref())
+    }
+
+    pub fn unsign_object<T>(&self, signed_object: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign(signed_object) {
+            Ok(value) => self.decode_object(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// [`Ed25519Signer`] with the same timestamp framing [`TimestampSigner`]
+/// adds on top of [`BaseSigner`].
+pub struct Ed25519TimestampSigner {
+    inner: Ed25519Signer,
+}
+
+impl Ed25519TimestampSigner {
+    pub fn new(
+        signing_key: ed25519_dalek::SigningKey,
+        serializer: Box<dyn PayloadSerializer>,
+    ) -> Self {
+        Self {
+            inner: Ed25519Signer::new(signing_key, serializer),
+        }
+    }
+}
+
+/// [`Ed25519Verifier`] with the same timestamp framing [`TimestampSigner`]
+/// adds on top of [`BaseSigner`] — the verify-only counterpart to
+/// [`Ed25519TimestampSigner`].
+pub struct Ed25519TimestampVerifier {
+    inner: Ed25519Verifier,
+}
+
+impl Ed25519TimestampVerifier {
+    pub fn new(
+        verifying_key: ed25519_dalek::VerifyingKey,
+        serializer: Box<dyn PayloadSerializer>,
+    ) -> Self {
+        Self {
+            inner: Ed25519Verifier::new(verifying_key, serializer),
+        }
+    }
+
+    pub fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
+        self.inner.unsign(signed_value).and_then(strip_timestamp)
+    }
+
+    pub fn unsign_with_age(
+        &self,
+        signed_value: String,
+        max_age: Duration,
+    ) -> Result<String, SignatureError> {
+        self.inner
+            .unsign(signed_value)
+            .and_then(|timestamped_value| check_timestamp(timestamped_value, max_age))
+    }
+
+    pub fn unsign_object<T>(&self, signed_object: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign(signed_object) {
+            Ok(value) => self.inner.decode_object(value),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn unsign_object_with_age<T>(
+        &self,
+        signed_value: String,
+        max_age: Duration,
+    ) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign_with_age(signed_value, max_age) {
+            Ok(value) => self.inner.decode_object(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Signer for Ed25519TimestampSigner {
+    fn sign(&self, value: String) -> String {
+        self.inner.sign(wrap_with_timestamp(value))
+    }
+    fn unsign(&self, signed_value: String) -> Result<String, SignatureError> {
+        self.inner.unsign(signed_value).and_then(strip_timestamp)
+    }
+
+    fn sign_object<T>(&self, obj: T, compress: bool) -> String
+    where
+        T: Serialize,
+    {
+        let value = self.inner.encode_object(obj, compress);
+        self.sign(value)
+    }
+
+    fn unsign_object<T>(&self, signed_object: String) -> Result<T, SignatureError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.unsign(signed_object) {
+            Ok(value) => self.inner.decode_object(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl TimedSigner for Ed25519TimestampSigner {
+    fn unsign_with_age(
+        &self,
+        signed_value: String,
+        max_age: Duration,
+    ) -> Result<String, SignatureError> {
+        self.inner
+            .unsign(signed_value)
+            .and_then(|timestamped_value| check_timestamp(timestamped_value, max_age))
+    }
+
     fn unsign_object_with_age<T>(
         &self,
         signed_value: String,
@@ -269,23 +768,233 @@ impl TimedSigner for TimestampSigner {
     }
 }
 
-pub fn dumps<T>(obj: T, key: &[u8], salt: &[u8], compress: bool) -> String
+pub fn dumps<T>(
+    obj: T,
+    key: &[u8],
+    fallback_keys: &[&[u8]],
+    salt: &[u8],
+    compress: bool,
+    key_derivation: KeyDerivation,
+    serializer: Box<dyn PayloadSerializer>,
+    digest_algorithm: DigestAlgorithm,
+) -> String
 where
     T: Serialize,
 {
-    let signer = TimestampSigner::new(key, salt);
+    let signer = TimestampSigner::new(
+        key,
+        fallback_keys,
+        salt,
+        key_derivation,
+        serializer,
+        digest_algorithm,
+    );
     signer.sign_object(obj, compress)
 }
 
 pub fn loads<T>(
     signed_value: String,
     key: &[u8],
+    fallback_keys: &[&[u8]],
     salt: &[u8],
     max_age: Duration,
+    key_derivation: KeyDerivation,
+    serializer: Box<dyn PayloadSerializer>,
+    digest_algorithm: DigestAlgorithm,
 ) -> Result<T, SignatureError>
 where
     T: DeserializeOwned,
 {
-    let signer = TimestampSigner::new(key, salt);
+    let signer = TimestampSigner::new(
+        key,
+        fallback_keys,
+        salt,
+        key_derivation,
+        serializer,
+        digest_algorithm,
+    );
     signer.unsign_object_with_age(signed_value, max_age)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_signer() -> BaseSigner {
+        BaseSigner::new(
+            b"secret",
+            &[],
+            b"salt",
+            KeyDerivation::DjangoConcat,
+            Box::new(JsonSerializer),
+            DigestAlgorithm::Sha256,
+        )
+    }
+
+    fn timestamp_signer() -> TimestampSigner {
+        TimestampSigner::new(
+            b"secret",
+            &[],
+            b"salt",
+            KeyDerivation::DjangoConcat,
+            Box::new(JsonSerializer),
+            DigestAlgorithm::Sha256,
+        )
+    }
+
+    fn signer_with(key_derivation: KeyDerivation, digest_algorithm: DigestAlgorithm) -> BaseSigner {
+        BaseSigner::new(
+            b"secret",
+            &[],
+            b"salt",
+            key_derivation,
+            Box::new(JsonSerializer),
+            digest_algorithm,
+        )
+    }
+
+    #[test]
+    fn round_trips_with_concat_key_derivation() {
+        let signer = signer_with(KeyDerivation::Concat, DigestAlgorithm::Sha256);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn round_trips_with_django_concat_key_derivation() {
+        let signer = signer_with(KeyDerivation::DjangoConcat, DigestAlgorithm::Sha256);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn round_trips_with_hmac_key_derivation() {
+        let signer = signer_with(KeyDerivation::Hmac, DigestAlgorithm::Sha256);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn round_trips_with_no_key_derivation() {
+        let signer = signer_with(KeyDerivation::None, DigestAlgorithm::Sha256);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn fallback_keys_let_a_rotated_signer_verify_tokens_from_the_old_key() {
+        let old_key_signer = BaseSigner::new(
+            b"old-secret",
+            &[],
+            b"salt",
+            KeyDerivation::DjangoConcat,
+            Box::new(JsonSerializer),
+            DigestAlgorithm::Sha256,
+        );
+        let token_from_old_key = old_key_signer.sign("hello".to_string());
+
+        let rotated_signer = BaseSigner::new(
+            b"new-secret",
+            &[b"old-secret"],
+            b"salt",
+            KeyDerivation::DjangoConcat,
+            Box::new(JsonSerializer),
+            DigestAlgorithm::Sha256,
+        );
+        // A token minted with the new key verifies...
+        let token_from_new_key = rotated_signer.sign("hello".to_string());
+        assert_eq!(rotated_signer.unsign(token_from_new_key).unwrap(), "hello");
+        // ...and a token minted before rotation still verifies via the
+        // fallback key, so rotating the signing key doesn't invalidate
+        // outstanding tokens.
+        assert_eq!(
+            rotated_signer.unsign(token_from_old_key).unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn round_trips_with_sha256_digest() {
+        let signer = signer_with(KeyDerivation::DjangoConcat, DigestAlgorithm::Sha256);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn round_trips_with_sha384_digest() {
+        let signer = signer_with(KeyDerivation::DjangoConcat, DigestAlgorithm::Sha384);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn round_trips_with_sha512_digest() {
+        let signer = signer_with(KeyDerivation::DjangoConcat, DigestAlgorithm::Sha512);
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(signer.unsign(signed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn canonical_json_serializer_produces_identical_bytes_regardless_of_key_order() {
+        let serializer = CanonicalJsonSerializer;
+        let a = serde_json::json!({"b": 1, "a": 2, "c": {"y": 1, "x": 2}});
+        let b = serde_json::json!({"a": 2, "c": {"x": 2, "y": 1}, "b": 1});
+        assert_eq!(serializer.serialize(&a), serializer.serialize(&b));
+    }
+
+    #[test]
+    fn ed25519_signer_round_trips_and_detects_tampering() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let signer = Ed25519Signer::new(signing_key, Box::new(JsonSerializer));
+        let verifier = Ed25519Verifier::new(signer.verifying_key(), Box::new(JsonSerializer));
+
+        let signed = signer.sign("hello".to_string());
+        assert_eq!(verifier.unsign(signed.clone()).unwrap(), "hello");
+
+        let tampered = signed.replacen("hello", "jello", 1);
+        assert!(matches!(
+            verifier.unsign(tampered),
+            Err(SignatureError::InvalidSignature)
+        ));
+    }
+
+    // decode_object used to reach for .unwrap() on attacker-controlled input;
+    // these lock in that malformed payloads now come back as an `Err`
+    // instead of panicking, at each place decoding can fail.
+    #[test]
+    fn unsign_object_rejects_invalid_base64_instead_of_panicking() {
+        let signer = base_signer();
+        let signed = signer.sign("not-valid-base64!!!".to_string());
+        let result: Result<serde_json::Value, SignatureError> = signer.unsign_object(signed);
+        assert!(matches!(result, Err(SignatureError::FormatError)));
+    }
+
+    #[test]
+    fn unsign_object_rejects_invalid_zlib_stream_instead_of_panicking() {
+        let signer = base_signer();
+        let garbage = base64::encode_config(b"not a real zlib stream", base64::URL_SAFE_NO_PAD);
+        let signed = signer.sign(format!(".{}", garbage));
+        let result: Result<serde_json::Value, SignatureError> = signer.unsign_object(signed);
+        assert!(matches!(result, Err(SignatureError::ObjectFormatError)));
+    }
+
+    #[test]
+    fn unsign_object_rejects_non_utf8_payload_after_decompress_instead_of_panicking() {
+        let signer = base_signer();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[0xff, 0xfe, 0xfd]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let encoded = base64::encode_config(compressed, base64::URL_SAFE_NO_PAD);
+        let signed = signer.sign(format!(".{}", encoded));
+        let result: Result<serde_json::Value, SignatureError> = signer.unsign_object(signed);
+        assert!(matches!(result, Err(SignatureError::ObjectFormatError)));
+    }
+
+    #[test]
+    fn timestamp_signer_unsign_object_rejects_invalid_base64_instead_of_panicking() {
+        let signer = timestamp_signer();
+        let signed = signer.sign("not-valid-base64!!!".to_string());
+        let result: Result<serde_json::Value, SignatureError> = signer.unsign_object(signed);
+        assert!(matches!(result, Err(SignatureError::FormatError)));
+    }
+}
@@ -1,28 +1,263 @@
+use pyo3::create_exception;
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::*;
 
-use crate::dumps;
+use crate::{
+    dumps, loads, BaseSigner, CanonicalJsonSerializer, DigestAlgorithm, Duration, JsonSerializer,
+    KeyDerivation, PayloadSerializer, SignatureError, Signer, TimestampSigner,
+};
+
+create_exception!(django_signing, MissingSeparatorError, PyValueError);
+create_exception!(django_signing, FormatError, PyValueError);
+create_exception!(django_signing, InvalidSignatureError, PyValueError);
+create_exception!(django_signing, MissingTimestampError, PyValueError);
+create_exception!(django_signing, TimestampFormatError, PyValueError);
+create_exception!(django_signing, SignatureExpiredError, PyValueError);
+create_exception!(django_signing, ObjectFormatError, PyValueError);
+
+fn parse_key_derivation(key_derivation: &str) -> PyResult<KeyDerivation> {
+    match key_derivation {
+        "concat" => Ok(KeyDerivation::Concat),
+        "django_concat" => Ok(KeyDerivation::DjangoConcat),
+        "hmac" => Ok(KeyDerivation::Hmac),
+        "none" => Ok(KeyDerivation::None),
+        other => Err(PyValueError::new_err(format!(
+            "unknown key_derivation {:?}, expected one of \"concat\", \"django_concat\", \"hmac\", \"none\"",
+            other
+        ))),
+    }
+}
+
+fn fallback_key_refs(fallback_keys: &[Vec<u8>]) -> Vec<&[u8]> {
+    fallback_keys.iter().map(|key| key.as_slice()).collect()
+}
+
+fn serializer_for(canonical_json: bool) -> Box<dyn PayloadSerializer> {
+    if canonical_json {
+        Box::new(CanonicalJsonSerializer)
+    } else {
+        Box::new(JsonSerializer)
+    }
+}
+
+fn parse_digest_algorithm(digest_algorithm: &str) -> PyResult<DigestAlgorithm> {
+    match digest_algorithm {
+        "sha256" => Ok(DigestAlgorithm::Sha256),
+        "sha384" => Ok(DigestAlgorithm::Sha384),
+        "sha512" => Ok(DigestAlgorithm::Sha512),
+        other => Err(PyValueError::new_err(format!(
+            "unknown digest_algorithm {:?}, expected one of \"sha256\", \"sha384\", \"sha512\"",
+            other
+        ))),
+    }
+}
+
+fn signature_error_to_py_err(error: SignatureError) -> PyErr {
+    match error {
+        SignatureError::MissingSeparator => {
+            MissingSeparatorError::new_err("no ':' found in the signed value")
+        }
+        SignatureError::FormatError => FormatError::new_err("signature or payload is not valid base64"),
+        SignatureError::InvalidSignature => {
+            InvalidSignatureError::new_err("signature does not match the value")
+        }
+        SignatureError::MissingTimestamp => {
+            MissingTimestampError::new_err("no timestamp found in the signed value")
+        }
+        SignatureError::TimestampFormatError => {
+            TimestampFormatError::new_err("timestamp is not a valid base62 Unix time")
+        }
+        SignatureError::SignatureExpired => {
+            SignatureExpiredError::new_err("signature timestamp is older than max_age")
+        }
+        SignatureError::ObjectFormatError => {
+            ObjectFormatError::new_err("payload could not be decompressed or is not valid JSON")
+        }
+    }
+}
 
 #[pyfunction]
 #[pyo3(name = "dumps")]
+#[pyo3(signature = (obj, key, salt, compress, key_derivation="django_concat", fallback_keys=Vec::new(), canonical_json=false, digest_algorithm="sha256"))]
 fn dumps_py(
     py: Python,
     obj: PyObject,
     key: &[u8],
     salt: &[u8],
     compress: bool,
+    key_derivation: &str,
+    fallback_keys: Vec<Vec<u8>>,
+    canonical_json: bool,
+    digest_algorithm: &str,
 ) -> pyo3::PyResult<String> {
-    match to_serde_value(py, &obj) {
-        Ok(obj) => Ok(dumps(obj, key, salt, compress)),
-        Err(e) => Err(e),
+    let key_derivation = parse_key_derivation(key_derivation)?;
+    let digest_algorithm = parse_digest_algorithm(digest_algorithm)?;
+    let obj = to_serde_value(py, &obj)?;
+    Ok(dumps(
+        obj,
+        key,
+        &fallback_key_refs(&fallback_keys),
+        salt,
+        compress,
+        key_derivation,
+        serializer_for(canonical_json),
+        digest_algorithm,
+    ))
+}
+
+#[pyfunction]
+#[pyo3(name = "loads")]
+#[pyo3(signature = (signed_value, key, salt, max_age, key_derivation="django_concat", fallback_keys=Vec::new(), canonical_json=false, digest_algorithm="sha256"))]
+fn loads_py(
+    py: Python,
+    signed_value: String,
+    key: &[u8],
+    salt: &[u8],
+    max_age: i64,
+    key_derivation: &str,
+    fallback_keys: Vec<Vec<u8>>,
+    canonical_json: bool,
+    digest_algorithm: &str,
+) -> pyo3::PyResult<PyObject> {
+    let key_derivation = parse_key_derivation(key_derivation)?;
+    let digest_algorithm = parse_digest_algorithm(digest_algorithm)?;
+    let obj: serde_json::Value = loads(
+        signed_value,
+        key,
+        &fallback_key_refs(&fallback_keys),
+        salt,
+        Duration::seconds(max_age),
+        key_derivation,
+        serializer_for(canonical_json),
+        digest_algorithm,
+    )
+    .map_err(signature_error_to_py_err)?;
+    Ok(from_serde_value(py, &obj))
+}
+
+/// Python wrapper around [`crate::BaseSigner`].
+#[pyclass(name = "Signer")]
+struct PySigner {
+    inner: BaseSigner,
+}
+
+#[pymethods]
+impl PySigner {
+    #[new]
+    #[pyo3(signature = (key, salt, key_derivation="django_concat", fallback_keys=Vec::new(), canonical_json=false, digest_algorithm="sha256"))]
+    fn new(
+        key: &[u8],
+        salt: &[u8],
+        key_derivation: &str,
+        fallback_keys: Vec<Vec<u8>>,
+        canonical_json: bool,
+        digest_algorithm: &str,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: BaseSigner::new(
+                key,
+                &fallback_key_refs(&fallback_keys),
+                salt,
+                parse_key_derivation(key_derivation)?,
+                serializer_for(canonical_json),
+                parse_digest_algorithm(digest_algorithm)?,
+            ),
+        })
+    }
+
+    fn sign(&self, value: String) -> String {
+        self.inner.sign(value)
+    }
+
+    fn unsign(&self, signed_value: String) -> PyResult<String> {
+        self.inner
+            .unsign(signed_value)
+            .map_err(signature_error_to_py_err)
+    }
+
+    fn sign_object(&self, py: Python, obj: PyObject, compress: bool) -> PyResult<String> {
+        let obj = to_serde_value(py, &obj)?;
+        Ok(self.inner.sign_object(obj, compress))
+    }
+
+    fn unsign_object(&self, py: Python, signed_object: String) -> PyResult<PyObject> {
+        let obj: serde_json::Value = self
+            .inner
+            .unsign_object(signed_object)
+            .map_err(signature_error_to_py_err)?;
+        Ok(from_serde_value(py, &obj))
+    }
+}
+
+/// Python wrapper around [`crate::TimestampSigner`].
+#[pyclass(name = "TimestampSigner")]
+struct PyTimestampSigner {
+    inner: TimestampSigner,
+}
+
+#[pymethods]
+impl PyTimestampSigner {
+    #[new]
+    #[pyo3(signature = (key, salt, key_derivation="django_concat", fallback_keys=Vec::new(), canonical_json=false, digest_algorithm="sha256"))]
+    fn new(
+        key: &[u8],
+        salt: &[u8],
+        key_derivation: &str,
+        fallback_keys: Vec<Vec<u8>>,
+        canonical_json: bool,
+        digest_algorithm: &str,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: TimestampSigner::new(
+                key,
+                &fallback_key_refs(&fallback_keys),
+                salt,
+                parse_key_derivation(key_derivation)?,
+                serializer_for(canonical_json),
+                parse_digest_algorithm(digest_algorithm)?,
+            ),
+        })
+    }
+
+    fn sign(&self, value: String) -> String {
+        self.inner.sign(value)
+    }
+
+    fn unsign(&self, signed_value: String) -> PyResult<String> {
+        self.inner
+            .unsign(signed_value)
+            .map_err(signature_error_to_py_err)
+    }
+
+    fn sign_object(&self, py: Python, obj: PyObject, compress: bool) -> PyResult<String> {
+        let obj = to_serde_value(py, &obj)?;
+        Ok(self.inner.sign_object(obj, compress))
+    }
+
+    fn unsign_object(&self, py: Python, signed_object: String) -> PyResult<PyObject> {
+        let obj: serde_json::Value = self
+            .inner
+            .unsign_object(signed_object)
+            .map_err(signature_error_to_py_err)?;
+        Ok(from_serde_value(py, &obj))
     }
 }
 
 #[pymodule]
 #[pyo3(name = "django_signing")]
-fn django_signing_py(_py: Python, m: &PyModule) -> pyo3::PyResult<()> {
+fn django_signing_py(py: Python, m: &PyModule) -> pyo3::PyResult<()> {
     m.add_function(wrap_pyfunction!(dumps_py, m)?)?;
+    m.add_function(wrap_pyfunction!(loads_py, m)?)?;
+    m.add_class::<PySigner>()?;
+    m.add_class::<PyTimestampSigner>()?;
+    m.add("MissingSeparatorError", py.get_type::<MissingSeparatorError>())?;
+    m.add("FormatError", py.get_type::<FormatError>())?;
+    m.add("InvalidSignatureError", py.get_type::<InvalidSignatureError>())?;
+    m.add("MissingTimestampError", py.get_type::<MissingTimestampError>())?;
+    m.add("TimestampFormatError", py.get_type::<TimestampFormatError>())?;
+    m.add("SignatureExpiredError", py.get_type::<SignatureExpiredError>())?;
+    m.add("ObjectFormatError", py.get_type::<ObjectFormatError>())?;
     Ok(())
 }
 
@@ -121,3 +356,55 @@ fn to_serde_value(py: Python, obj: &PyObject) -> PyResult<serde_json::Value> {
         obj.as_ref(py).get_type().repr()?
     )))
 }
+
+/// The inverse of [`to_serde_value`]: turns a decoded JSON value back into a
+/// native Python object.
+fn from_serde_value(py: Python, value: &serde_json::Value) -> PyObject {
+    match value {
+        serde_json::Value::Null => py.None(),
+        serde_json::Value::Bool(b) => b.into_py(py),
+        serde_json::Value::Number(n) => {
+            if let Some(v) = n.as_u64() {
+                v.into_py(py)
+            } else if let Some(v) = n.as_i64() {
+                v.into_py(py)
+            } else {
+                n.as_f64().into_py(py)
+            }
+        }
+        serde_json::Value::String(s) => s.into_py(py),
+        serde_json::Value::Array(values) => PyList::new(
+            py,
+            values.iter().map(|value| from_serde_value(py, value)),
+        )
+        .into_py(py),
+        serde_json::Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, from_serde_value(py, value)).unwrap();
+            }
+            dict.into_py(py)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_and_from_serde_value_round_trips_common_json_shapes() {
+        Python::with_gil(|py| {
+            let value = serde_json::json!({
+                "name": "ferris",
+                "tags": ["rust", "crab"],
+                "count": 3,
+                "ok": true,
+                "missing": null,
+            });
+            let py_obj = from_serde_value(py, &value);
+            let round_tripped = to_serde_value(py, &py_obj).unwrap();
+            assert_eq!(round_tripped, value);
+        });
+    }
+}